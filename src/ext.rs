@@ -1,16 +1,16 @@
-use {IntrusiveIterator, FromIntrusiveIterator};
+use {Traversal, FromIntrusiveIterator};
 
-/// Extension methods for Intrusive Iterators
-pub trait IntrusiveIteratorExt<T> : IntrusiveIterator<T> {
-    fn map<O, F: FnMut(T) -> O>(self, f: F) -> Map<Self, F> {
+/// Extension methods for Traversals
+pub trait TraversalExt : Traversal {
+    fn map<O, F: FnMut(Self::Item) -> O>(self, f: F) -> Map<Self, F> {
         Map { iter: self, closure: f }
     }
 
-    fn filter<F: FnMut(&T) -> bool>(self, pred: F) -> Filter<Self, F> {
+    fn filter<F: FnMut(&Self::Item) -> bool>(self, pred: F) -> Filter<Self, F> {
         Filter { iter: self, predicate: pred }
     }
 
-    fn filter_map<O, F: FnMut(T) -> Option<O>>(self, pred: F) -> FilterMap<Self, F> {
+    fn filter_map<O, F: FnMut(Self::Item) -> Option<O>>(self, pred: F) -> FilterMap<Self, F> {
         FilterMap { iter: self, predicate: pred }
     }
 
@@ -26,50 +26,173 @@ pub trait IntrusiveIteratorExt<T> : IntrusiveIterator<T> {
         Take { iter: self, n: n }
     }
 
-    fn skip_while<F: FnMut(T) -> bool>(self, pred: F) -> SkipWhile<Self, F> {
+    fn skip_while<F: FnMut(&Self::Item) -> bool>(self, pred: F) -> SkipWhile<Self, F> {
         SkipWhile { iter: self, predicate: pred }
     }
 
-    fn take_while<F: FnMut(T) -> bool>(self, pred: F) -> TakeWhile<Self, F> {
+    fn take_while<F: FnMut(&Self::Item) -> bool>(self, pred: F) -> TakeWhile<Self, F> {
         TakeWhile { iter: self, predicate: pred }
     }
 
-    fn inspect<F: FnMut(&T)>(self, f: F) -> Inspect<Self, F> {
+    fn inspect<F: FnMut(&Self::Item)>(self, f: F) -> Inspect<Self, F> {
         Inspect { iter: self, closure: f }
     }
 
-    fn flat_map<O, U: Iterator<O>, F: FnMut(T) -> U>(self, f: F) -> FlatMap<Self, F> {
+    fn flat_map<U: Traversal, F: FnMut(Self::Item) -> U>(self, f: F) -> FlatMap<Self, F> {
         FlatMap { iter: self, producer: f }
     }
 
-    fn chain<O: IntrusiveIterator<T>>(self, other: O) -> Chain<Self, O> {
+    fn chain<O: Traversal<Item=Self::Item>>(self, other: O) -> Chain<Self, O> {
         Chain { one: self, two: other }
     }
 
+    fn scan<St, O, F: FnMut(&mut St, Self::Item) -> Option<O>>(self, initial: St, f: F) -> Scan<Self, St, F> {
+        Scan { iter: self, state: initial, closure: f }
+    }
+
+    fn step_by(self, n: uint) -> StepBy<Self> {
+        StepBy { iter: self, n: n }
+    }
+
+    fn intersperse(self, sep: Self::Item) -> Intersperse<Self> where Self::Item: Clone {
+        Intersperse { iter: self, sep: sep }
+    }
+
+    fn cloned<'a, U>(self) -> Cloned<Self> where Self: Traversal<Item=&'a U>, U: Clone {
+        Cloned { iter: self }
+    }
+
+    fn copied<'a, U>(self) -> Copied<Self> where Self: Traversal<Item=&'a U>, U: Copy {
+        Copied { iter: self }
+    }
+
+    fn chunks(self, n: uint) -> Chunks<Self> {
+        Chunks { iter: self, n: n }
+    }
+
     fn count(self) -> uint {
         let mut count = 0;
-        self.iterate(|_| { count += 1; });
+        self.foreach(|_| { count += 1; false });
         count
     }
 
-    fn collect<D: FromIntrusiveIterator<T>>(self) -> D {
+    fn collect<D: FromIntrusiveIterator<Self::Item>>(self) -> D {
         FromIntrusiveIterator::collect(self)
     }
+
+    fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, mut f: F) -> B {
+        let mut accum = Some(init);
+        self.foreach(|t| {
+            let a = accum.take().unwrap();
+            accum = Some(f(a, t));
+            false
+        });
+        accum.unwrap()
+    }
+
+    fn sum(self) -> Self::Item
+    where Self::Item: ::std::num::Zero + Add<Self::Item, Self::Item> {
+        self.fold(::std::num::Zero::zero(), |acc, x| acc + x)
+    }
+
+    fn product(self) -> Self::Item
+    where Self::Item: ::std::num::One + Mul<Self::Item, Self::Item> {
+        self.fold(::std::num::One::one(), |acc, x| acc * x)
+    }
+
+    fn min(self) -> Option<Self::Item> where Self::Item: Ord {
+        self.fold(None, |min, x| match min {
+            None => Some(x),
+            Some(m) => Some(if x < m { x } else { m })
+        })
+    }
+
+    fn max(self) -> Option<Self::Item> where Self::Item: Ord {
+        self.fold(None, |max, x| match max {
+            None => Some(x),
+            Some(m) => Some(if x > m { x } else { m })
+        })
+    }
+
+    fn min_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, mut f: F) -> Option<Self::Item> {
+        self.fold(None, |min, x| match min {
+            None => Some(x),
+            Some(m) => if f(&x) < f(&m) { Some(x) } else { Some(m) }
+        })
+    }
+
+    fn max_by_key<B: Ord, F: FnMut(&Self::Item) -> B>(self, mut f: F) -> Option<Self::Item> {
+        self.fold(None, |max, x| match max {
+            None => Some(x),
+            Some(m) => if f(&x) > f(&m) { Some(x) } else { Some(m) }
+        })
+    }
+
+    fn any<P: FnMut(Self::Item) -> bool>(self, mut pred: P) -> bool {
+        let mut found = false;
+        self.foreach(|t| {
+            if pred(t) { found = true; true } else { false }
+        });
+        found
+    }
+
+    fn all<P: FnMut(Self::Item) -> bool>(self, mut pred: P) -> bool {
+        let mut result = true;
+        self.foreach(|t| {
+            if pred(t) { false } else { result = false; true }
+        });
+        result
+    }
+
+    fn find<P: FnMut(&Self::Item) -> bool>(self, mut pred: P) -> Option<Self::Item> {
+        let mut hit = None;
+        self.foreach(|t| {
+            if pred(&t) { hit = Some(t); true } else { false }
+        });
+        hit
+    }
+
+    fn find_map<O, F: FnMut(Self::Item) -> Option<O>>(self, mut f: F) -> Option<O> {
+        let mut hit = None;
+        self.foreach(|t| {
+            match f(t) {
+                Some(o) => { hit = Some(o); true },
+                None => false
+            }
+        });
+        hit
+    }
+
+    fn position<P: FnMut(Self::Item) -> bool>(self, mut pred: P) -> Option<uint> {
+        let mut index = 0;
+        let mut pos = None;
+        self.foreach(|t| {
+            if pred(t) {
+                pos = Some(index);
+                true
+            } else {
+                index += 1;
+                false
+            }
+        });
+        pos
+    }
 }
 
-impl<T, I: IntrusiveIterator<T>> IntrusiveIteratorExt<T> for I {}
+impl<I: Traversal> TraversalExt for I {}
 
-/// An IntrusiveIterator that maps over the contents of
-/// another IntrusiveIterator.
+/// A Traversal that maps over the contents of another Traversal.
 pub struct Map<I, F> {
     iter: I,
     closure: F
 }
 
-impl<T, O, I: IntrusiveIterator<T>, F: FnMut(T) -> O> IntrusiveIterator<O> for Map<I, F> {
-    fn traverse<F1: FnMut(O) -> bool>(self, mut f: F1) {
+impl<O, I: Traversal, F: FnMut(I::Item) -> O> Traversal for Map<I, F> {
+    type Item = O;
+
+    fn foreach<F1: FnMut(O) -> bool>(self, mut f: F1) {
         let mut closure = self.closure;
-        self.iter.traverse(move |t: T| {
+        self.iter.foreach(move |t| {
             f(closure(t))
         });
     }
@@ -80,11 +203,13 @@ pub struct Filter<I, F> {
     predicate: F
 }
 
-impl<T, I, F> IntrusiveIterator<T> for Filter<I, F>
-where I: IntrusiveIterator<T>, F: FnMut(&T) -> bool {
-    fn traverse<F1: FnMut(T) -> bool>(self, mut f: F1) {
+impl<I, F> Traversal for Filter<I, F>
+where I: Traversal, F: FnMut(&I::Item) -> bool {
+    type Item = I::Item;
+
+    fn foreach<F1: FnMut(I::Item) -> bool>(self, mut f: F1) {
         let mut predicate = self.predicate;
-        self.iter.traverse(move |t: T| {
+        self.iter.foreach(move |t| {
             if predicate(&t) { f(t) } else { false }
         });
     }
@@ -95,11 +220,13 @@ pub struct FilterMap<I, F> {
     predicate: F
 }
 
-impl<T, O, I, F> IntrusiveIterator<O> for FilterMap<I, F>
-where I: IntrusiveIterator<T>, F: FnMut(T) -> Option<O> {
-    fn traverse<F1: FnMut(O) -> bool>(self, mut f: F1) {
+impl<O, I, F> Traversal for FilterMap<I, F>
+where I: Traversal, F: FnMut(I::Item) -> Option<O> {
+    type Item = O;
+
+    fn foreach<F1: FnMut(O) -> bool>(self, mut f: F1) {
         let mut predicate = self.predicate;
-        self.iter.traverse(move |t: T| {
+        self.iter.foreach(move |t| {
             match predicate(t) {
                 Some(o) => f(o),
                 None => false
@@ -110,11 +237,13 @@ where I: IntrusiveIterator<T>, F: FnMut(T) -> Option<O> {
 
 pub struct Enumerate<I>(I);
 
-impl<T, I> IntrusiveIterator<(uint, T)> for Enumerate<I>
-where I: IntrusiveIterator<T> {
-    fn traverse<F1: FnMut((uint, T)) -> bool>(self, mut f: F1) {
+impl<I> Traversal for Enumerate<I>
+where I: Traversal {
+    type Item = (uint, I::Item);
+
+    fn foreach<F1: FnMut((uint, I::Item)) -> bool>(self, mut f: F1) {
         let mut counter = 0;
-        self.0.traverse(|t: T| {
+        self.0.foreach(|t| {
             let res = f((counter, t));
             counter += 1;
             res
@@ -127,13 +256,15 @@ pub struct Skip<I> {
     n: uint
 }
 
-impl<T, I> IntrusiveIterator<T> for Skip<I>
-where I: IntrusiveIterator<T> {
-    fn traverse<F1: FnMut(T) -> bool>(self, mut f: F1) {
+impl<I> Traversal for Skip<I>
+where I: Traversal {
+    type Item = I::Item;
+
+    fn foreach<F1: FnMut(I::Item) -> bool>(self, mut f: F1) {
         let mut counter = 0;
         let n = self.n;
 
-        self.iter.traverse(|t: T| {
+        self.iter.foreach(|t| {
             if counter != n {
                 counter += 1;
                 true
@@ -149,13 +280,15 @@ pub struct Take<I> {
     n: uint
 }
 
-impl<T, I> IntrusiveIterator<T> for Take<I>
-where I: IntrusiveIterator<T> {
-    fn traverse<F1: FnMut(T) -> bool>(self, mut f: F1) {
+impl<I> Traversal for Take<I>
+where I: Traversal {
+    type Item = I::Item;
+
+    fn foreach<F1: FnMut(I::Item) -> bool>(self, mut f: F1) {
         let mut counter = 0;
         let n = self.n;
 
-        self.iter.traverse(|t: T| {
+        self.iter.foreach(|t| {
             if counter != n {
                 counter += 1;
                 f(t)
@@ -171,12 +304,14 @@ pub struct SkipWhile<I, F> {
     predicate: F
 }
 
-impl<T, I, F> IntrusiveIterator<T> for SkipWhile<I, F>
-where I: IntrusiveIterator<T>, F: FnMut(&T) -> bool {
-    fn traverse<F1: FnMut(T) -> bool>(self, mut f: F1) {
+impl<I, F> Traversal for SkipWhile<I, F>
+where I: Traversal, F: FnMut(&I::Item) -> bool {
+    type Item = I::Item;
+
+    fn foreach<F1: FnMut(I::Item) -> bool>(self, mut f: F1) {
         let mut predicate = self.predicate;
         let mut flag = false;
-        self.iter.traverse(move |t: T| {
+        self.iter.foreach(move |t| {
             // Done skipping
             if flag {
                 if !predicate(&t) {
@@ -195,11 +330,13 @@ pub struct TakeWhile<I, F> {
     predicate: F
 }
 
-impl<T, I, F> IntrusiveIterator<T> for TakeWhile<I, F>
-where I: IntrusiveIterator<T>, F: FnMut(&T) -> bool {
-    fn traverse<F1: FnMut(T) -> bool>(self, mut f: F1) {
+impl<I, F> Traversal for TakeWhile<I, F>
+where I: Traversal, F: FnMut(&I::Item) -> bool {
+    type Item = I::Item;
+
+    fn foreach<F1: FnMut(I::Item) -> bool>(self, mut f: F1) {
         let mut predicate = self.predicate;
-        self.iter.traverse(move |t: T| {
+        self.iter.foreach(move |t| {
             if predicate(&t) { f(t) } else { true }
         });
     }
@@ -210,11 +347,13 @@ pub struct Inspect<I, F> {
     closure: F
 }
 
-impl<T, I, F> IntrusiveIterator<T> for Inspect<I, F>
-where I: IntrusiveIterator<T>, F: FnMut(&T) {
-    fn traverse<F1: FnMut(T) -> bool>(self, mut f: F1) {
+impl<I, F> Traversal for Inspect<I, F>
+where I: Traversal, F: FnMut(&I::Item) {
+    type Item = I::Item;
+
+    fn foreach<F1: FnMut(I::Item) -> bool>(self, mut f: F1) {
         let mut closure = self.closure;
-        self.iter.traverse(move |t: T| {
+        self.iter.foreach(move |t| {
             closure(&t);
             f(t)
         });
@@ -226,16 +365,18 @@ pub struct Chain<I, O> {
     two: O
 }
 
-impl<T, I, O> IntrusiveIterator<T> for Chain<I, O>
-where I: IntrusiveIterator<T>, O: IntrusiveIterator<T> {
-    fn traverse<F1: FnMut(T) -> bool>(self, mut f: F1) {
+impl<I, O> Traversal for Chain<I, O>
+where I: Traversal, O: Traversal<Item=I::Item> {
+    type Item = I::Item;
+
+    fn foreach<F1: FnMut(I::Item) -> bool>(self, mut f: F1) {
         let mut flag = false;
-        self.one.traverse(|t: T| {
+        self.one.foreach(|t| {
             flag = f(t); flag
         });
 
         if !flag {
-            self.two.traverse(|t: T| {
+            self.two.foreach(|t| {
                 f(t)
             });
         }
@@ -247,15 +388,17 @@ pub struct FlatMap<I, F> {
     producer: F
 }
 
-impl<T, O, U, I, F> IntrusiveIterator<O> for FlatMap<I, F>
-where I: IntrusiveIterator<T>,
-      F: FnMut(T) -> U,
-      U: IntrusiveIterator<O> {
-    fn traverse<F1: FnMut(O) -> bool>(self, mut f: F1) {
+impl<U, I, F> Traversal for FlatMap<I, F>
+where I: Traversal,
+      F: FnMut(I::Item) -> U,
+      U: Traversal {
+    type Item = U::Item;
+
+    fn foreach<F1: FnMut(U::Item) -> bool>(self, mut f: F1) {
         let mut producer = self.producer;
         let mut flag = false;
-        self.iter.traverse(|t: T| {
-            producer(t).traverse(|o: O| {
+        self.iter.foreach(|t| {
+            producer(t).foreach(|o| {
                 flag = f(o); flag
             });
             flag
@@ -263,3 +406,127 @@ where I: IntrusiveIterator<T>,
     }
 }
 
+pub struct Scan<I, St, F> {
+    iter: I,
+    state: St,
+    closure: F
+}
+
+impl<O, St, I, F> Traversal for Scan<I, St, F>
+where I: Traversal, F: FnMut(&mut St, I::Item) -> Option<O> {
+    type Item = O;
+
+    fn foreach<F1: FnMut(O) -> bool>(self, mut f: F1) {
+        let mut state = self.state;
+        let mut closure = self.closure;
+        self.iter.foreach(move |t| {
+            match closure(&mut state, t) {
+                Some(o) => f(o),
+                None => true
+            }
+        });
+    }
+}
+
+pub struct StepBy<I> {
+    iter: I,
+    n: uint
+}
+
+impl<I> Traversal for StepBy<I>
+where I: Traversal {
+    type Item = I::Item;
+
+    fn foreach<F1: FnMut(I::Item) -> bool>(self, mut f: F1) {
+        debug_assert!(self.n != 0, "step_by: step must be non-zero");
+        let n = self.n;
+        let mut counter = 0;
+        self.iter.foreach(|t| {
+            let res = if counter % n == 0 { f(t) } else { false };
+            counter += 1;
+            res
+        })
+    }
+}
+
+pub struct Intersperse<I> where I: Traversal {
+    iter: I,
+    sep: I::Item
+}
+
+impl<I> Traversal for Intersperse<I>
+where I: Traversal, I::Item: Clone {
+    type Item = I::Item;
+
+    fn foreach<F1: FnMut(I::Item) -> bool>(self, mut f: F1) {
+        let sep = self.sep;
+        let mut first = true;
+        self.iter.foreach(|t| {
+            if first {
+                first = false;
+                f(t)
+            } else {
+                if f(sep.clone()) { true } else { f(t) }
+            }
+        });
+    }
+}
+
+pub struct Cloned<I> {
+    iter: I
+}
+
+impl<'a, U, I> Traversal for Cloned<I>
+where I: Traversal<Item=&'a U>, U: Clone {
+    type Item = U;
+
+    fn foreach<F1: FnMut(U) -> bool>(self, mut f: F1) {
+        self.iter.foreach(move |t| f(t.clone()));
+    }
+}
+
+pub struct Copied<I> {
+    iter: I
+}
+
+impl<'a, U, I> Traversal for Copied<I>
+where I: Traversal<Item=&'a U>, U: Copy {
+    type Item = U;
+
+    fn foreach<F1: FnMut(U) -> bool>(self, mut f: F1) {
+        self.iter.foreach(move |t| f(*t));
+    }
+}
+
+/// A Traversal that batches the contents of another Traversal into
+/// `Vec`s of up to `n` consecutive elements.
+pub struct Chunks<I> {
+    iter: I,
+    n: uint
+}
+
+impl<I> Traversal for Chunks<I>
+where I: Traversal {
+    type Item = Vec<I::Item>;
+
+    fn foreach<F1: FnMut(Vec<I::Item>) -> bool>(self, mut f: F1) {
+        let n = self.n;
+        let mut buf = Vec::new();
+        let mut stop = false;
+        self.iter.foreach(|t| {
+            buf.push(t);
+            if buf.len() == n {
+                let full = ::std::mem::replace(&mut buf, Vec::new());
+                stop = f(full);
+                stop
+            } else {
+                false
+            }
+        });
+
+        // Flush any short remainder so no elements are dropped.
+        if !stop && !buf.is_empty() {
+            f(buf);
+        }
+    }
+}